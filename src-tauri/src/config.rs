@@ -0,0 +1,167 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+const CONFIG_FILE: &str = "config.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    pub vault_dir: PathBuf,
+    pub theme: String,
+    pub recent_paths: Vec<String>,
+}
+
+impl Config {
+    fn defaults(app: &AppHandle) -> Result<Self, String> {
+        let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+        Ok(Self {
+            vault_dir: data_dir.join("vault"),
+            theme: "system".to_string(),
+            recent_paths: Vec::new(),
+        })
+    }
+}
+
+pub struct ConfigState(pub Mutex<Config>);
+
+fn config_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(CONFIG_FILE))
+}
+
+fn load_or_init(app: &AppHandle) -> Result<Config, String> {
+    let path = config_path(app)?;
+    match std::fs::read_to_string(&path) {
+        Ok(json) => serde_json::from_str(&json).map_err(|e| e.to_string()),
+        Err(_) => {
+            let config = Config::defaults(app)?;
+            save(app, &config)?;
+            Ok(config)
+        }
+    }
+}
+
+fn validate(config: &Config) -> Result<(), String> {
+    if config.theme.trim().is_empty() {
+        return Err("theme must not be empty".to_string());
+    }
+    if config.vault_dir.as_os_str().is_empty() {
+        return Err("vault_dir must not be empty".to_string());
+    }
+    if !config.vault_dir.is_absolute() {
+        return Err(format!(
+            "vault_dir must be an absolute path: {}",
+            config.vault_dir.display()
+        ));
+    }
+    Ok(())
+}
+
+/// Writes to a temp file in the same directory as `path` and fsyncs it
+/// before renaming over the destination, so a crash mid-write can't
+/// truncate `config.json`.
+fn save_to(path: &Path, config: &Config) -> Result<(), String> {
+    let parent = path
+        .parent()
+        .ok_or_else(|| "config path has no parent directory".to_string())?;
+    let json = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
+
+    let tmp_path = parent.join(format!(".{CONFIG_FILE}.tmp"));
+    {
+        let mut tmp_file = std::fs::File::create(&tmp_path).map_err(|e| e.to_string())?;
+        tmp_file
+            .write_all(json.as_bytes())
+            .map_err(|e| e.to_string())?;
+        tmp_file.sync_all().map_err(|e| e.to_string())?;
+    }
+    std::fs::rename(&tmp_path, path).map_err(|e| e.to_string())
+}
+
+fn save(app: &AppHandle, config: &Config) -> Result<(), String> {
+    save_to(&config_path(app)?, config)
+}
+
+/// Loads the config at startup (writing defaults on first run) and keeps it
+/// in managed state for the rest of the app's lifetime.
+pub fn init(app: &AppHandle) -> Result<(), String> {
+    let config = load_or_init(app)?;
+    app.manage(ConfigState(Mutex::new(config)));
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_config(state: State<ConfigState>) -> Result<Config, String> {
+    Ok(state.0.lock().map_err(|e| e.to_string())?.clone())
+}
+
+#[tauri::command]
+pub fn set_config(
+    app: AppHandle,
+    state: State<ConfigState>,
+    config: Config,
+) -> Result<(), String> {
+    validate(&config)?;
+    save(&app, &config)?;
+    *state.0.lock().map_err(|e| e.to_string())? = config;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_config_path(app: AppHandle) -> Result<String, String> {
+    Ok(config_path(&app)?.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(vault_dir: PathBuf) -> Config {
+        Config {
+            vault_dir,
+            theme: "system".to_string(),
+            recent_paths: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn validate_rejects_empty_theme() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = sample(dir.path().to_path_buf());
+        config.theme = "  ".to_string();
+        assert!(validate(&config).is_err());
+    }
+
+    #[test]
+    fn validate_rejects_non_absolute_vault_dir() {
+        let config = sample(PathBuf::from("relative/vault"));
+        assert!(validate(&config).is_err());
+    }
+
+    #[test]
+    fn validate_accepts_a_well_formed_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = sample(dir.path().to_path_buf());
+        assert!(validate(&config).is_ok());
+    }
+
+    #[test]
+    fn save_to_writes_and_overwrites_atomically() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILE);
+
+        let first = sample(dir.path().join("vault"));
+        save_to(&path, &first).unwrap();
+        let loaded: Config = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(loaded.vault_dir, first.vault_dir);
+
+        let mut second = first.clone();
+        second.theme = "dark".to_string();
+        save_to(&path, &second).unwrap();
+        let loaded: Config = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(loaded.theme, "dark");
+    }
+}
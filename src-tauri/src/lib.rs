@@ -1,6 +1,22 @@
 use std::fs;
-use std::path::PathBuf;
-use tauri::Manager;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+use tauri::{Manager, State};
+
+mod config;
+mod meta;
+mod scan;
+mod scope;
+mod search;
+mod watch;
+
+use config::{get_config, get_config_path, set_config};
+use meta::{read_meta, write_meta};
+use scan::scan_dir;
+use scope::{allow_dir, revoke_dir, ScopeState};
+use search::search_files;
+use watch::{unwatch_dir, watch_dir, WatchState};
 
 #[tauri::command]
 fn get_data_dir(app: tauri::AppHandle) -> Result<String, String> {
@@ -12,56 +28,140 @@ fn get_data_dir(app: tauri::AppHandle) -> Result<String, String> {
 }
 
 #[tauri::command]
-fn read_file(path: String) -> Result<String, String> {
-    fs::read_to_string(&path).map_err(|e| e.to_string())
+fn read_file(state: State<ScopeState>, path: String) -> Result<String, String> {
+    let resolved = scope::resolve(&state, &path)?;
+    fs::read_to_string(resolved).map_err(|e| e.to_string())
 }
 
-#[tauri::command]
-fn write_file(path: String, content: String) -> Result<(), String> {
-    if let Some(parent) = PathBuf::from(&path).parent() {
-        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+/// Derives the `<name>.bak` path a backup of `path` is written to.
+fn backup_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".bak");
+    path.with_file_name(file_name)
+}
+
+/// Writes `content` to `resolved` via temp-file-then-rename so a crash
+/// mid-write can never leave readers looking at a truncated or empty file,
+/// optionally snapshotting the previous version to `<name>.bak` first.
+fn write_file_atomic(resolved: &Path, content: &str, keep_backup: bool) -> Result<(), String> {
+    let parent = resolved
+        .parent()
+        .ok_or_else(|| "path has no parent directory".to_string())?;
+    fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+
+    if keep_backup && resolved.exists() {
+        fs::copy(resolved, backup_path(resolved)).map_err(|e| e.to_string())?;
+    }
+
+    let tmp_name = format!(
+        ".{}.tmp",
+        resolved.file_name().and_then(|n| n.to_str()).unwrap_or("write")
+    );
+    let tmp_path = parent.join(tmp_name);
+    {
+        let mut tmp_file = fs::File::create(&tmp_path).map_err(|e| e.to_string())?;
+        tmp_file
+            .write_all(content.as_bytes())
+            .map_err(|e| e.to_string())?;
+        tmp_file.sync_all().map_err(|e| e.to_string())?;
     }
-    fs::write(&path, content).map_err(|e| e.to_string())
+    fs::rename(&tmp_path, resolved).map_err(|e| e.to_string())
 }
 
-#[tauri::command]
-fn delete_file(path: String) -> Result<(), String> {
-    let p = PathBuf::from(&path);
-    if p.exists() {
-        fs::remove_file(&p).map_err(|e| e.to_string())
-    } else {
-        Ok(())
+fn restore_backup_at(resolved: &Path) -> Result<(), String> {
+    let backup = backup_path(resolved);
+    if !backup.exists() {
+        return Err(format!("no backup found for {}", resolved.display()));
     }
+    fs::rename(backup, resolved).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn write_file(
+    state: State<ScopeState>,
+    path: String,
+    content: String,
+    keep_backup: Option<bool>,
+) -> Result<(), String> {
+    let resolved = scope::resolve(&state, &path)?;
+    write_file_atomic(&resolved, &content, keep_backup.unwrap_or(false))
+}
+
+#[tauri::command]
+fn restore_backup(state: State<ScopeState>, path: String) -> Result<(), String> {
+    let resolved = scope::resolve(&state, &path)?;
+    restore_backup_at(&resolved)
 }
 
 #[tauri::command]
-fn list_dir(path: String) -> Result<Vec<String>, String> {
-    let p = PathBuf::from(&path);
-    if !p.exists() {
+fn delete_file(state: State<ScopeState>, path: String) -> Result<(), String> {
+    let resolved = scope::resolve(&state, &path)?;
+    if resolved.exists() {
+        fs::remove_file(&resolved).map_err(|e| e.to_string())?;
+    }
+    meta::delete_sidecar(&resolved)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DirEntry {
+    pub name: String,
+    pub is_dir: bool,
+    pub has_meta: bool,
+}
+
+/// Lists `resolved`'s immediate children, matching `scan_dir`'s filtering:
+/// sidecar metadata/backup/temp files are internal artifacts, not user
+/// entries, and each remaining entry reports whether it has sidecar
+/// metadata attached.
+fn list_dir_at(resolved: &Path) -> Result<Vec<DirEntry>, String> {
+    if !resolved.exists() {
         return Ok(Vec::new());
     }
-    let entries = fs::read_dir(&p).map_err(|e| e.to_string())?;
-    let names: Vec<String> = entries
+    let entries = fs::read_dir(resolved).map_err(|e| e.to_string())?;
+    let dir_entries: Vec<DirEntry> = entries
         .filter_map(|e| e.ok())
-        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|e| !scan::is_internal_artifact(&e.path()))
+        .map(|e| {
+            let is_dir = e.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            let has_meta = !is_dir && meta::has_sidecar(&e.path());
+            DirEntry {
+                name: e.file_name().to_string_lossy().to_string(),
+                is_dir,
+                has_meta,
+            }
+        })
         .collect();
-    Ok(names)
+    Ok(dir_entries)
 }
 
 #[tauri::command]
-fn ensure_dir(path: String) -> Result<(), String> {
-    fs::create_dir_all(&path).map_err(|e| e.to_string())
+fn list_dir(state: State<ScopeState>, path: String) -> Result<Vec<DirEntry>, String> {
+    let resolved = scope::resolve(&state, &path)?;
+    list_dir_at(&resolved)
 }
 
 #[tauri::command]
-fn file_exists(path: String) -> bool {
-    PathBuf::from(&path).exists()
+fn ensure_dir(state: State<ScopeState>, path: String) -> Result<(), String> {
+    let resolved = scope::resolve(&state, &path)?;
+    fs::create_dir_all(resolved).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn file_exists(state: State<ScopeState>, path: String) -> Result<bool, String> {
+    let resolved = scope::resolve(&state, &path)?;
+    Ok(resolved.exists())
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_log::Builder::default().build())
+        .manage(WatchState::default())
+        .setup(|app| {
+            config::init(app.handle())?;
+            scope::init(app.handle())?;
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             get_data_dir,
             read_file,
@@ -70,7 +170,85 @@ pub fn run() {
             list_dir,
             ensure_dir,
             file_exists,
+            scan_dir,
+            get_config,
+            set_config,
+            get_config_path,
+            allow_dir,
+            revoke_dir,
+            restore_backup,
+            watch_dir,
+            unwatch_dir,
+            search_files,
+            read_meta,
+            write_meta,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_file_atomic_creates_parent_dirs_and_writes_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("nested").join("note.md");
+
+        write_file_atomic(&target, "hello", false).unwrap();
+
+        assert_eq!(fs::read_to_string(&target).unwrap(), "hello");
+        assert!(!backup_path(&target).exists());
+    }
+
+    #[test]
+    fn write_file_atomic_keeps_backup_and_restore_recovers_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("note.md");
+        fs::write(&target, "first version").unwrap();
+
+        write_file_atomic(&target, "second version", true).unwrap();
+        assert_eq!(fs::read_to_string(&target).unwrap(), "second version");
+        assert_eq!(
+            fs::read_to_string(backup_path(&target)).unwrap(),
+            "first version"
+        );
+
+        write_file_atomic(&target, "third version", false).unwrap();
+        restore_backup_at(&target).unwrap();
+        assert_eq!(fs::read_to_string(&target).unwrap(), "first version");
+    }
+
+    #[test]
+    fn restore_backup_without_a_backup_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("note.md");
+        fs::write(&target, "only version").unwrap();
+
+        assert!(restore_backup_at(&target).is_err());
+    }
+
+    #[test]
+    fn list_dir_hides_internal_artifacts_and_reports_sidecar_presence() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("note.md"), "hello").unwrap();
+        fs::write(dir.path().join("note.md.meta.json"), "{}").unwrap();
+        fs::write(dir.path().join("note.md.bak"), "old").unwrap();
+        fs::create_dir(dir.path().join("sub")).unwrap();
+
+        let mut entries = list_dir_at(dir.path()).unwrap();
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+        assert_eq!(
+            entries.iter().map(|e| e.name.as_str()).collect::<Vec<_>>(),
+            vec!["note.md", "sub"]
+        );
+        let note = entries.iter().find(|e| e.name == "note.md").unwrap();
+        assert!(!note.is_dir);
+        assert!(note.has_meta);
+        let sub = entries.iter().find(|e| e.name == "sub").unwrap();
+        assert!(sub.is_dir);
+        assert!(!sub.has_meta);
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde_json::Value;
+use tauri::State;
+
+use crate::scope::{self, ScopeState};
+
+/// Serializes sidecar read-modify-write sequences so two near-simultaneous
+/// `write_meta` calls can't clobber each other's keys.
+static SIDECAR_LOCK: Mutex<()> = Mutex::new(());
+
+fn sidecar_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".meta.json");
+    path.with_file_name(file_name)
+}
+
+fn load_sidecar(path: &Path) -> HashMap<String, Value> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_sidecar(path: &Path, data: &HashMap<String, Value>) -> Result<(), String> {
+    if data.is_empty() {
+        if path.exists() {
+            fs::remove_file(path).map_err(|e| e.to_string())?;
+        }
+        return Ok(());
+    }
+    let json = serde_json::to_string_pretty(data).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn read_meta(
+    state: State<ScopeState>,
+    path: String,
+    key: String,
+) -> Result<Option<Value>, String> {
+    let resolved = scope::resolve(&state, &path)?;
+    Ok(load_sidecar(&sidecar_path(&resolved)).get(&key).cloned())
+}
+
+#[tauri::command]
+pub fn write_meta(
+    state: State<ScopeState>,
+    path: String,
+    key: String,
+    value: Value,
+) -> Result<(), String> {
+    let resolved = scope::resolve(&state, &path)?;
+    let sidecar_file = sidecar_path(&resolved);
+    let _guard = SIDECAR_LOCK.lock().map_err(|e| e.to_string())?;
+    let mut sidecar = load_sidecar(&sidecar_file);
+    sidecar.insert(key, value);
+    save_sidecar(&sidecar_file, &sidecar)
+}
+
+/// Removes `path`'s sidecar metadata, if any. Called from `delete_file` so
+/// orphaned metadata doesn't accumulate once the file it describes is gone.
+pub fn delete_sidecar(path: &Path) -> Result<(), String> {
+    let sidecar_file = sidecar_path(path);
+    if sidecar_file.exists() {
+        fs::remove_file(sidecar_file).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Whether `path` has any sidecar metadata, for `scan_dir` to surface.
+pub fn has_sidecar(path: &Path) -> bool {
+    sidecar_path(path).exists()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn sidecar_round_trips_and_reports_presence() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("note.md");
+        fs::write(&target, "hello").unwrap();
+
+        assert!(!has_sidecar(&target));
+
+        let sidecar_file = sidecar_path(&target);
+        let mut data = load_sidecar(&sidecar_file);
+        data.insert("tags".to_string(), json!(["a", "b"]));
+        save_sidecar(&sidecar_file, &data).unwrap();
+
+        assert!(has_sidecar(&target));
+        let reloaded = load_sidecar(&sidecar_file);
+        assert_eq!(reloaded.get("tags"), Some(&json!(["a", "b"])));
+    }
+
+    #[test]
+    fn saving_an_empty_sidecar_removes_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("note.md");
+        fs::write(&target, "hello").unwrap();
+        let sidecar_file = sidecar_path(&target);
+        save_sidecar(&sidecar_file, &HashMap::from([("k".to_string(), json!(1))])).unwrap();
+        assert!(has_sidecar(&target));
+
+        save_sidecar(&sidecar_file, &HashMap::new()).unwrap();
+        assert!(!has_sidecar(&target));
+    }
+
+    #[test]
+    fn delete_sidecar_removes_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("note.md");
+        fs::write(&target, "hello").unwrap();
+        fs::write(sidecar_path(&target), "{}").unwrap();
+
+        assert!(has_sidecar(&target));
+        delete_sidecar(&target).unwrap();
+        assert!(!has_sidecar(&target));
+    }
+}
@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use tauri::{Manager, State};
+
+use crate::scope::{self, ScopeState};
+
+const DEFAULT_EXCLUDES: &[&str] = &["target", ".git", "node_modules"];
+const INDEX_FILE: &str = "scan-index.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScanEntry {
+    pub rel_path: String,
+    pub size: u64,
+    pub modified_secs: u64,
+    pub is_dir: bool,
+    pub kind: String,
+    pub has_meta: bool,
+}
+
+/// Keyed by canonical root path, then by relative path within that root, so
+/// scanning a second root can't make the first root's entries look removed
+/// (or vice versa).
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ScanIndex(HashMap<String, HashMap<String, ScanEntry>>);
+
+/// Canonical string key used to namespace a scanned root's entries in both
+/// the scan index and the search index.
+pub(crate) fn root_key(root: &Path) -> String {
+    root.to_string_lossy().replace('\\', "/")
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ScanDiff {
+    pub added: Vec<ScanEntry>,
+    pub modified: Vec<ScanEntry>,
+    pub removed: Vec<String>,
+    pub entries: Vec<ScanEntry>,
+}
+
+fn infer_kind(rel_path: &Path) -> String {
+    match rel_path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => ext.to_lowercase(),
+        None => "file".to_string(),
+    }
+}
+
+/// Sidecar/backup/temp files soulkit writes alongside a file (`.meta.json`
+/// from the metadata store, `.bak` from `write_file`'s backups, and its
+/// in-progress `.<name>.tmp` writes) aren't user content and shouldn't show
+/// up as scanned/searchable entries in their own right.
+pub(crate) fn is_internal_artifact(path: &Path) -> bool {
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    file_name.ends_with(".meta.json")
+        || file_name.ends_with(".bak")
+        || (file_name.starts_with('.') && file_name.ends_with(".tmp"))
+}
+
+/// Compiles `exclude` into a glob matcher. Each pattern is tested both
+/// against the full relative path (so `build/*` or `**/tmp` work) and
+/// against each individual path component (so a bare directory name like
+/// `target` keeps excluding that directory at any depth).
+fn build_exclude_matcher(exclude: &[String]) -> Result<GlobSet, String> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in exclude {
+        let pattern = pattern.trim_end_matches('/');
+        if pattern.is_empty() {
+            continue;
+        }
+        builder.add(Glob::new(pattern).map_err(|e| e.to_string())?);
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
+fn is_excluded(rel_path: &Path, matcher: &GlobSet) -> bool {
+    let rel_str = rel_path.to_string_lossy().replace('\\', "/");
+    matcher.is_match(&rel_str)
+        || rel_path
+            .components()
+            .any(|c| matcher.is_match(c.as_os_str().to_string_lossy().as_ref()))
+}
+
+fn index_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(INDEX_FILE))
+}
+
+fn load_index(path: &Path) -> ScanIndex {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_index(path: &Path, index: &ScanIndex) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(index).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
+
+/// Walks `root`, diffing what it finds against `stored`'s entry for this
+/// root (which it updates in place to reflect the new snapshot) and
+/// returning what changed. Other roots' entries in `stored` are untouched.
+fn diff_scan(
+    root: &Path,
+    max_depth: usize,
+    matcher: &GlobSet,
+    stored: &mut ScanIndex,
+) -> (Vec<ScanEntry>, Vec<ScanEntry>, Vec<String>) {
+    let key = root_key(root);
+    let previous = stored.0.remove(&key).unwrap_or_default();
+    let mut seen = HashMap::new();
+    let mut added = Vec::new();
+    let mut modified = Vec::new();
+
+    let mut walker = walkdir::WalkDir::new(root)
+        .max_depth(max_depth)
+        .follow_links(false)
+        .into_iter();
+
+    loop {
+        let entry = match walker.next() {
+            None => break,
+            Some(Ok(entry)) => entry,
+            Some(Err(_)) => continue,
+        };
+
+        let entry_path = entry.path();
+        let rel = match entry_path.strip_prefix(root) {
+            Ok(rel) if !rel.as_os_str().is_empty() => rel.to_path_buf(),
+            _ => continue,
+        };
+
+        if is_excluded(&rel, matcher) {
+            if entry.file_type().is_dir() {
+                walker.skip_current_dir();
+            }
+            continue;
+        }
+
+        if !entry.file_type().is_dir() && is_internal_artifact(entry_path) {
+            continue;
+        }
+
+        let metadata = match entry.metadata() {
+            Ok(metadata) => metadata,
+            Err(_) => continue,
+        };
+
+        let modified_secs = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        let rel_path = rel.to_string_lossy().replace('\\', "/");
+        let scan_entry = ScanEntry {
+            rel_path: rel_path.clone(),
+            size: metadata.len(),
+            modified_secs,
+            is_dir: metadata.is_dir(),
+            kind: if metadata.is_dir() {
+                "dir".to_string()
+            } else {
+                infer_kind(&rel)
+            },
+            has_meta: !metadata.is_dir() && crate::meta::has_sidecar(entry_path),
+        };
+
+        match previous.get(&rel_path) {
+            None => added.push(scan_entry.clone()),
+            Some(prev) if *prev != scan_entry => modified.push(scan_entry.clone()),
+            Some(_) => {}
+        }
+
+        seen.insert(rel_path, scan_entry);
+    }
+
+    let removed: Vec<String> = previous
+        .keys()
+        .filter(|rel_path| !seen.contains_key(*rel_path))
+        .cloned()
+        .collect();
+
+    stored.0.insert(key, seen);
+    (added, modified, removed)
+}
+
+/// Recursively walks `path`, diffing what it finds against the on-disk
+/// index from the previous scan so callers only have to handle what changed.
+#[tauri::command]
+pub fn scan_dir(
+    app: tauri::AppHandle,
+    scope: State<ScopeState>,
+    path: String,
+    max_depth: Option<usize>,
+    exclude: Option<Vec<String>>,
+) -> Result<ScanDiff, String> {
+    let root = scope::resolve(&scope, &path)?;
+    if !root.exists() {
+        return Err(format!("path does not exist: {path}"));
+    }
+    let exclude =
+        exclude.unwrap_or_else(|| DEFAULT_EXCLUDES.iter().map(|s| s.to_string()).collect());
+    let matcher = build_exclude_matcher(&exclude)?;
+    let max_depth = max_depth.unwrap_or(usize::MAX);
+
+    let idx_path = index_path(&app)?;
+    let mut stored = load_index(&idx_path);
+
+    let (added, modified, removed) = diff_scan(&root, max_depth, &matcher, &mut stored);
+
+    save_index(&idx_path, &stored)?;
+    crate::search::reindex_changed(&app, &root, &added, &modified, &removed)?;
+
+    let mut entries: Vec<ScanEntry> = stored
+        .0
+        .get(&root_key(&root))
+        .map(|by_rel_path| by_rel_path.values().cloned().collect())
+        .unwrap_or_default();
+    entries.sort_by(|a, b| a.rel_path.cmp(&b.rel_path));
+
+    Ok(ScanDiff {
+        added,
+        modified,
+        removed,
+        entries,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, contents: &str) {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn diff_scan_reports_added_modified_and_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(&root.join("keep.txt"), "unchanged");
+        write(&root.join("gone.txt"), "will be removed");
+
+        let matcher = build_exclude_matcher(&[]).unwrap();
+        let mut stored = ScanIndex::default();
+        let (added, _, _) = diff_scan(root, usize::MAX, &matcher, &mut stored);
+        assert_eq!(added.len(), 2);
+
+        write(&root.join("keep.txt"), "changed contents, different size");
+        fs::remove_file(root.join("gone.txt")).unwrap();
+        write(&root.join("new.txt"), "brand new");
+
+        let (added, modified, removed) = diff_scan(root, usize::MAX, &matcher, &mut stored);
+        assert_eq!(
+            added.iter().map(|e| e.rel_path.as_str()).collect::<Vec<_>>(),
+            vec!["new.txt"]
+        );
+        assert_eq!(
+            modified
+                .iter()
+                .map(|e| e.rel_path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["keep.txt"]
+        );
+        assert_eq!(removed, vec!["gone.txt".to_string()]);
+    }
+
+    #[test]
+    fn exclude_matcher_supports_globs_and_bare_names() {
+        let matcher =
+            build_exclude_matcher(&["*.log".to_string(), "target".to_string(), "**/tmp".to_string()])
+                .unwrap();
+        assert!(is_excluded(Path::new("app.log"), &matcher));
+        assert!(is_excluded(Path::new("nested/app.log"), &matcher));
+        assert!(is_excluded(Path::new("target"), &matcher));
+        assert!(is_excluded(Path::new("nested/target"), &matcher));
+        assert!(is_excluded(Path::new("a/b/tmp"), &matcher));
+        assert!(!is_excluded(Path::new("src/main.rs"), &matcher));
+    }
+
+    #[test]
+    fn scanning_a_second_root_does_not_disturb_the_first_roots_entries() {
+        let dir_a = tempfile::tempdir().unwrap();
+        let dir_b = tempfile::tempdir().unwrap();
+        write(&dir_a.path().join("a.txt"), "alpha");
+        write(&dir_b.path().join("b.txt"), "beta");
+
+        let matcher = build_exclude_matcher(&[]).unwrap();
+        let mut stored = ScanIndex::default();
+        diff_scan(dir_a.path(), usize::MAX, &matcher, &mut stored);
+
+        let (added, modified, removed) = diff_scan(dir_b.path(), usize::MAX, &matcher, &mut stored);
+        assert_eq!(
+            added.iter().map(|e| e.rel_path.as_str()).collect::<Vec<_>>(),
+            vec!["b.txt"]
+        );
+        assert!(modified.is_empty());
+        assert!(removed.is_empty());
+
+        assert!(stored.0[&root_key(dir_a.path())].contains_key("a.txt"));
+        assert!(stored.0[&root_key(dir_b.path())].contains_key("b.txt"));
+    }
+
+    #[test]
+    fn internal_artifacts_are_detected() {
+        assert!(is_internal_artifact(Path::new("note.md.meta.json")));
+        assert!(is_internal_artifact(Path::new("note.md.bak")));
+        assert!(is_internal_artifact(Path::new(".note.md.tmp")));
+        assert!(!is_internal_artifact(Path::new("note.md")));
+    }
+}
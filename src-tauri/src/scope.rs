@@ -0,0 +1,132 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager, State};
+
+pub struct ScopeState(pub Mutex<HashSet<PathBuf>>);
+
+/// Registers the app data dir as the only allowed root until the frontend
+/// grants access to further directories via `allow_dir`.
+pub fn init(app: &AppHandle) -> Result<(), String> {
+    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&data_dir).map_err(|e| e.to_string())?;
+    let mut roots = HashSet::new();
+    roots.insert(dunce::canonicalize(&data_dir).map_err(|e| e.to_string())?);
+    app.manage(ScopeState(Mutex::new(roots)));
+    Ok(())
+}
+
+/// Canonicalizes the nearest existing ancestor of `path` and re-attaches
+/// whatever tail doesn't exist yet, so callers can scope-check a path
+/// they're about to create (e.g. a new file) as well as one that exists.
+fn canonicalize_lenient(path: &Path) -> Result<PathBuf, String> {
+    let mut ancestor = path;
+    let mut tail: Vec<&std::ffi::OsStr> = Vec::new();
+    while !ancestor.exists() {
+        tail.push(
+            ancestor
+                .file_name()
+                .ok_or_else(|| format!("invalid path: {}", path.display()))?,
+        );
+        ancestor = ancestor
+            .parent()
+            .ok_or_else(|| format!("invalid path: {}", path.display()))?;
+    }
+    let mut canonical = dunce::canonicalize(ancestor).map_err(|e| e.to_string())?;
+    for part in tail.into_iter().rev() {
+        canonical.push(part);
+    }
+    Ok(canonical)
+}
+
+/// Resolves `path` to a canonical form and rejects it if it escapes every
+/// allowed root. This is the single choke point every file command routes
+/// through before touching disk.
+pub fn resolve(state: &ScopeState, path: &str) -> Result<PathBuf, String> {
+    let canonical = canonicalize_lenient(Path::new(path))?;
+    let roots = state.0.lock().map_err(|e| e.to_string())?;
+    if roots.iter().any(|root| canonical.starts_with(root)) {
+        Ok(canonical)
+    } else {
+        Err(format!(
+            "path escapes allowed roots: {}",
+            canonical.display()
+        ))
+    }
+}
+
+#[tauri::command]
+pub fn allow_dir(state: State<ScopeState>, path: String) -> Result<(), String> {
+    std::fs::create_dir_all(&path).map_err(|e| e.to_string())?;
+    let canonical = dunce::canonicalize(&path).map_err(|e| e.to_string())?;
+    state
+        .0
+        .lock()
+        .map_err(|e| e.to_string())?
+        .insert(canonical);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn revoke_dir(state: State<ScopeState>, path: String) -> Result<(), String> {
+    let canonical = dunce::canonicalize(&path).map_err(|e| e.to_string())?;
+    state.0.lock().map_err(|e| e.to_string())?.remove(&canonical);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with_root(root: &Path) -> ScopeState {
+        let mut roots = HashSet::new();
+        roots.insert(dunce::canonicalize(root).unwrap());
+        ScopeState(Mutex::new(roots))
+    }
+
+    #[test]
+    fn resolve_allows_a_path_under_a_registered_root() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("note.md"), "hi").unwrap();
+        let state = state_with_root(dir.path());
+
+        let resolved = resolve(&state, dir.path().join("note.md").to_str().unwrap()).unwrap();
+        assert_eq!(resolved, dunce::canonicalize(dir.path()).unwrap().join("note.md"));
+    }
+
+    #[test]
+    fn resolve_allows_a_not_yet_created_path_under_a_registered_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = state_with_root(dir.path());
+
+        let resolved = resolve(&state, dir.path().join("new.md").to_str().unwrap()).unwrap();
+        assert_eq!(resolved, dunce::canonicalize(dir.path()).unwrap().join("new.md"));
+    }
+
+    #[test]
+    fn resolve_rejects_a_path_outside_every_registered_root() {
+        let allowed = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        std::fs::write(outside.path().join("secret.txt"), "nope").unwrap();
+        let state = state_with_root(allowed.path());
+
+        let err = resolve(&state, outside.path().join("secret.txt").to_str().unwrap()).unwrap_err();
+        assert!(err.contains("escapes allowed roots"));
+    }
+
+    #[test]
+    fn resolve_rejects_traversal_back_out_of_the_root() {
+        let allowed = tempfile::tempdir().unwrap();
+        std::fs::create_dir(allowed.path().join("sub")).unwrap();
+        let state = state_with_root(allowed.path());
+
+        let escaping = allowed
+            .path()
+            .join("sub")
+            .join("..")
+            .join("..")
+            .join("outside.txt");
+        assert!(resolve(&state, escaping.to_str().unwrap()).is_err());
+    }
+}
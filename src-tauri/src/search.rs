@@ -0,0 +1,357 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+use crate::scan::{root_key, ScanEntry};
+use crate::scope::{self, ScopeState};
+
+const INDEX_FILE: &str = "search-index.json";
+const SNIFF_BYTES: usize = 8192;
+const SNIPPET_CHARS: usize = 160;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct Posting {
+    count: u32,
+    /// 1-based line numbers this term occurs on, so a query spanning
+    /// multiple terms can find the line where they best co-occur instead
+    /// of being stuck with whichever line the term happened to hit first.
+    lines: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct DocEntry {
+    total_terms: u32,
+    postings: HashMap<String, Posting>,
+}
+
+/// The inverted index for a single scanned root. Keeping one `RootIndex` per
+/// root (rather than one flat `docs` map) means scanning a second directory
+/// can't make the first root's entries look removed or vice versa.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RootIndex {
+    root: PathBuf,
+    docs: HashMap<String, DocEntry>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SearchIndex {
+    roots: HashMap<String, RootIndex>,
+    /// Root most recently reindexed by `scan_dir`, used as the default for
+    /// `search_files` calls that don't specify which root to search.
+    last_root: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    pub path: String,
+    pub line: u32,
+    pub snippet: String,
+    pub score: f64,
+}
+
+fn index_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(INDEX_FILE))
+}
+
+fn load_index(path: &Path) -> SearchIndex {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_index(path: &Path, index: &SearchIndex) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(index).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
+
+fn is_binary(bytes: &[u8]) -> bool {
+    bytes.iter().take(SNIFF_BYTES).any(|&b| b == 0)
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_lowercase())
+        .collect()
+}
+
+fn index_document(docs: &mut HashMap<String, DocEntry>, rel_path: &str, content: &str) {
+    let mut postings: HashMap<String, Posting> = HashMap::new();
+    let mut total_terms = 0u32;
+    for (line_no, line) in content.lines().enumerate() {
+        let line_number = line_no as u32 + 1;
+        for term in tokenize(line) {
+            total_terms += 1;
+            let posting = postings.entry(term).or_default();
+            posting.count += 1;
+            posting.lines.push(line_number);
+        }
+    }
+    docs.insert(rel_path.to_string(), DocEntry { total_terms, postings });
+}
+
+/// `line` is 1-based, matching `SearchHit::line`.
+fn read_snippet(path: &Path, line: u32) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
+    let text = content.lines().nth((line as usize).saturating_sub(1))?.trim();
+    if text.chars().count() > SNIPPET_CHARS {
+        Some(format!("{}…", text.chars().take(SNIPPET_CHARS).collect::<String>()))
+    } else {
+        Some(text.to_string())
+    }
+}
+
+/// Reindexes exactly the files `scan_dir` reported as added/modified/removed,
+/// so search stays current without ever re-reading the whole tree.
+pub fn reindex_changed(
+    app: &AppHandle,
+    root: &Path,
+    added: &[ScanEntry],
+    modified: &[ScanEntry],
+    removed: &[String],
+) -> Result<(), String> {
+    let idx_path = index_path(app)?;
+    let mut index = load_index(&idx_path);
+    let key = root_key(root);
+    let root_index = index.roots.entry(key.clone()).or_insert_with(|| RootIndex {
+        root: root.to_path_buf(),
+        docs: HashMap::new(),
+    });
+
+    for entry in added.iter().chain(modified.iter()) {
+        if entry.is_dir {
+            continue;
+        }
+        let Ok(bytes) = fs::read(root.join(&entry.rel_path)) else {
+            continue;
+        };
+        if is_binary(&bytes) {
+            root_index.docs.remove(&entry.rel_path);
+            continue;
+        }
+        let content = String::from_utf8_lossy(&bytes);
+        index_document(&mut root_index.docs, &entry.rel_path, &content);
+    }
+
+    for rel_path in removed {
+        root_index.docs.remove(rel_path);
+    }
+
+    index.last_root = Some(key);
+    save_index(&idx_path, &index)
+}
+
+/// Picks the 1-based line where the most *distinct* query terms co-occur,
+/// rather than just the first occurrence of whichever term happens to hit
+/// earliest. Counts terms present per line, not raw occurrences, so a line
+/// repeating one term can't outrank a line where several different terms
+/// actually co-occur.
+fn best_line(doc: &DocEntry, terms: &[String]) -> u32 {
+    let mut terms_per_line: HashMap<u32, HashSet<&str>> = HashMap::new();
+    for term in terms {
+        if let Some(posting) = doc.postings.get(term) {
+            for &line in &posting.lines {
+                terms_per_line.entry(line).or_default().insert(term.as_str());
+            }
+        }
+    }
+    let mut candidates: Vec<(u32, usize)> = terms_per_line
+        .into_iter()
+        .map(|(line, matched)| (line, matched.len()))
+        .collect();
+    candidates.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    candidates.first().map(|(line, _)| *line).unwrap_or(1)
+}
+
+/// Ranks documents by term-frequency weighted by inverse document frequency.
+/// A quoted query requires every term to appear in the document (phrase-ish
+/// matching); an unquoted query is a plain term-OR ranked by relevance.
+fn search_index(index: &RootIndex, query: &str, limit: usize) -> Vec<SearchHit> {
+    let root = &index.root;
+
+    let trimmed = query.trim();
+    let is_phrase = trimmed.len() >= 2 && trimmed.starts_with('"') && trimmed.ends_with('"');
+    let terms = tokenize(trimmed.trim_matches('"'));
+    if terms.is_empty() {
+        return Vec::new();
+    }
+
+    let total_docs = index.docs.len().max(1) as f64;
+    let mut scores: HashMap<String, f64> = HashMap::new();
+    for term in &terms {
+        let doc_freq = index
+            .docs
+            .values()
+            .filter(|doc| doc.postings.contains_key(term))
+            .count();
+        if doc_freq == 0 {
+            continue;
+        }
+        let idf = (total_docs / doc_freq as f64).ln() + 1.0;
+        for (rel_path, doc) in &index.docs {
+            if let Some(posting) = doc.postings.get(term) {
+                let tf = posting.count as f64 / doc.total_terms.max(1) as f64;
+                *scores.entry(rel_path.clone()).or_insert(0.0) += tf * idf;
+            }
+        }
+    }
+
+    let mut ranked: Vec<(String, f64)> = scores
+        .into_iter()
+        .filter(|(rel_path, _)| {
+            !is_phrase || terms.iter().all(|t| index.docs[rel_path].postings.contains_key(t))
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    ranked.truncate(limit);
+
+    ranked
+        .into_iter()
+        .map(|(rel_path, score)| {
+            let doc = &index.docs[&rel_path];
+            let line = best_line(doc, &terms);
+            let snippet = read_snippet(&root.join(&rel_path), line).unwrap_or_default();
+            SearchHit {
+                path: rel_path,
+                line,
+                snippet,
+                score,
+            }
+        })
+        .collect()
+}
+
+/// Searches the index for `root` (the directory most recently `scan_dir`-ed,
+/// or an explicit `path` when a caller is juggling more than one).
+#[tauri::command]
+pub fn search_files(
+    app: AppHandle,
+    scope: State<ScopeState>,
+    query: String,
+    path: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<SearchHit>, String> {
+    let idx_path = index_path(&app)?;
+    let index = load_index(&idx_path);
+
+    let key = match path {
+        Some(path) => root_key(&scope::resolve(&scope, &path)?),
+        None => index
+            .last_root
+            .clone()
+            .ok_or_else(|| "no directory has been scanned yet".to_string())?,
+    };
+    let root_index = index
+        .roots
+        .get(&key)
+        .ok_or_else(|| format!("no search index for {key}"))?;
+
+    Ok(search_index(root_index, &query, limit.unwrap_or(20)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_index(root: &Path, docs: &[(&str, &str)]) -> RootIndex {
+        let mut index = RootIndex {
+            root: root.to_path_buf(),
+            docs: HashMap::new(),
+        };
+        for (rel_path, content) in docs {
+            index_document(&mut index.docs, rel_path, content);
+        }
+        index
+    }
+
+    #[test]
+    fn ranks_by_term_frequency_weighted_by_idf_and_reports_one_based_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let a_content = "rust rust rust other\n";
+        let b_content = "rust other other other\n";
+        fs::write(root.join("a.md"), a_content).unwrap();
+        fs::write(root.join("b.md"), b_content).unwrap();
+        let index = build_index(root, &[("a.md", a_content), ("b.md", b_content)]);
+
+        let hits = search_index(&index, "rust", 10);
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].path, "a.md");
+        assert_eq!(hits[0].line, 1);
+        assert!(hits[0].score > hits[1].score);
+    }
+
+    #[test]
+    fn phrase_query_requires_every_term_present() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let a_content = "alpha beta\n";
+        let b_content = "alpha only\n";
+        fs::write(root.join("a.md"), a_content).unwrap();
+        fs::write(root.join("b.md"), b_content).unwrap();
+        let index = build_index(root, &[("a.md", a_content), ("b.md", b_content)]);
+
+        let hits = search_index(&index, "\"alpha beta\"", 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].path, "a.md");
+    }
+
+    #[test]
+    fn best_line_prefers_the_line_where_terms_co_occur() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let content = "alpha only\nalpha and beta together\n";
+        fs::write(root.join("a.md"), content).unwrap();
+        let index = build_index(root, &[("a.md", content)]);
+
+        let hits = search_index(&index, "alpha beta", 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].line, 2);
+    }
+
+    #[test]
+    fn best_line_counts_distinct_terms_not_raw_occurrences() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let content = "alpha alpha alpha alpha\nalpha beta\n";
+        fs::write(root.join("a.md"), content).unwrap();
+        let index = build_index(root, &[("a.md", content)]);
+
+        let hits = search_index(&index, "alpha beta", 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].line, 2);
+    }
+
+    #[test]
+    fn separate_roots_stay_independent_in_the_same_index() {
+        let dir_a = tempfile::tempdir().unwrap();
+        let dir_b = tempfile::tempdir().unwrap();
+
+        let mut index = SearchIndex::default();
+
+        let key_a = root_key(dir_a.path());
+        let root_a = index.roots.entry(key_a.clone()).or_insert_with(|| RootIndex {
+            root: dir_a.path().to_path_buf(),
+            docs: HashMap::new(),
+        });
+        index_document(&mut root_a.docs, "note.md", "alpha");
+
+        let key_b = root_key(dir_b.path());
+        let root_b = index.roots.entry(key_b.clone()).or_insert_with(|| RootIndex {
+            root: dir_b.path().to_path_buf(),
+            docs: HashMap::new(),
+        });
+        index_document(&mut root_b.docs, "note.md", "beta");
+
+        assert!(index.roots[&key_a].docs["note.md"].postings.contains_key("alpha"));
+        assert!(!index.roots[&key_a].docs["note.md"].postings.contains_key("beta"));
+        assert!(index.roots[&key_b].docs["note.md"].postings.contains_key("beta"));
+        assert!(!index.roots[&key_b].docs["note.md"].postings.contains_key("alpha"));
+    }
+}
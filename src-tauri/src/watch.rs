@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::scope::{self, ScopeState};
+
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum ChangeKind {
+    Created,
+    Modified,
+    Removed,
+    Renamed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct FsChange {
+    kind: ChangeKind,
+    path: String,
+}
+
+fn classify(kind: &EventKind) -> ChangeKind {
+    match kind {
+        EventKind::Create(_) => ChangeKind::Created,
+        EventKind::Modify(notify::event::ModifyKind::Name(_)) => ChangeKind::Renamed,
+        EventKind::Modify(_) => ChangeKind::Modified,
+        EventKind::Remove(_) => ChangeKind::Removed,
+        _ => ChangeKind::Modified,
+    }
+}
+
+/// Keeps the underlying watcher alive; dropping it (on `unwatch_dir`) closes
+/// its event channel, which unwinds the debounce thread below.
+struct WatcherHandle {
+    _watcher: RecommendedWatcher,
+}
+
+#[derive(Default)]
+pub struct WatchState(pub Mutex<HashMap<PathBuf, WatcherHandle>>);
+
+#[tauri::command]
+pub fn watch_dir(
+    app: AppHandle,
+    scope: State<ScopeState>,
+    watchers: State<WatchState>,
+    path: String,
+) -> Result<(), String> {
+    let resolved = scope::resolve(&scope, &path)?;
+    let mut active = watchers.0.lock().map_err(|e| e.to_string())?;
+    if active.contains_key(&resolved) {
+        return Ok(());
+    }
+
+    let (tx, rx) = mpsc::channel::<Event>();
+    let mut watcher = notify::recommended_watcher(move |result: notify::Result<Event>| {
+        if let Ok(event) = result {
+            let _ = tx.send(event);
+        }
+    })
+    .map_err(|e| e.to_string())?;
+    watcher
+        .watch(&resolved, RecursiveMode::Recursive)
+        .map_err(|e| e.to_string())?;
+
+    // Trailing-edge debounce: events keep resetting the `recv_timeout` wait,
+    // so a burst only flushes once `DEBOUNCE` passes with no new events.
+    // Continuous sub-DEBOUNCE churn (e.g. a large copy) delays flushing
+    // until the burst actually quiets down, not just after a fixed age.
+    let app_handle = app.clone();
+    thread::spawn(move || {
+        let mut pending: HashMap<String, ChangeKind> = HashMap::new();
+        loop {
+            match rx.recv_timeout(DEBOUNCE) {
+                Ok(event) => {
+                    let kind = classify(&event.kind);
+                    for path in event.paths {
+                        pending.insert(path.to_string_lossy().to_string(), kind.clone());
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    for (path, kind) in pending.drain() {
+                        let _ = app_handle.emit("fs-change", FsChange { kind, path });
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    });
+
+    active.insert(resolved, WatcherHandle { _watcher: watcher });
+    Ok(())
+}
+
+#[tauri::command]
+pub fn unwatch_dir(
+    scope: State<ScopeState>,
+    watchers: State<WatchState>,
+    path: String,
+) -> Result<(), String> {
+    let resolved = scope::resolve(&scope, &path)?;
+    watchers.0.lock().map_err(|e| e.to_string())?.remove(&resolved);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{CreateKind, ModifyKind, RemoveKind, RenameMode};
+
+    #[test]
+    fn classify_maps_notify_event_kinds_to_change_kinds() {
+        assert_eq!(
+            classify(&EventKind::Create(CreateKind::File)),
+            ChangeKind::Created
+        );
+        assert_eq!(
+            classify(&EventKind::Modify(ModifyKind::Name(RenameMode::Both))),
+            ChangeKind::Renamed
+        );
+        assert_eq!(
+            classify(&EventKind::Modify(ModifyKind::Data(
+                notify::event::DataChange::Content
+            ))),
+            ChangeKind::Modified
+        );
+        assert_eq!(
+            classify(&EventKind::Remove(RemoveKind::File)),
+            ChangeKind::Removed
+        );
+    }
+}